@@ -0,0 +1,137 @@
+#![cfg(feature = "blocking")]
+
+//! A synchronous Alpha Vantage client, for use outside an async runtime. Enabled by the
+//! `blocking` cargo feature.
+
+use anyhow::Result;
+
+use crate::error::QaError;
+use crate::historical::HistoricalData;
+use crate::request_client::{ReqwestRequestClient, RequestClient};
+use crate::third_party_api::alpha_vantage::{self, AlphaVantageRangeFunction};
+use crate::third_party_api::throttle::Throttle;
+
+/// A synchronous counterpart to [`alpha_vantage::Client`], for use outside an async
+/// runtime. The HTTP transport is abstracted behind [`RequestClient`] so it can be swapped
+/// for another backend or a mock in tests. Like [`alpha_vantage::Client`], it supports
+/// rate-limit throttling and retries, configured via [`Client::builder`].
+#[derive(Debug)]
+pub struct Client {
+    api_key: String,
+    http: Box<dyn RequestClient>,
+    throttle: Throttle,
+}
+
+impl Client {
+    /// Creates a new blocking client for the Alpha Vantage API authenticated with
+    /// `api_key`, using the default `reqwest`-backed transport, with no rate-limit
+    /// throttling or retries. Use [`Client::builder`] to configure those.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Client::builder().build(api_key)
+    }
+
+    /// Creates a new blocking client using a custom [`RequestClient`] backend, e.g. a mock
+    /// for tests or a different HTTP library, with no rate-limit throttling or retries. Use
+    /// [`Client::builder`] to configure those.
+    pub fn with_request_client(api_key: impl Into<String>, http: impl RequestClient + 'static) -> Self {
+        Client::builder().build_with_request_client(api_key, http)
+    }
+
+    /// Starts building a client with rate-limit throttling and/or retry behavior, e.g.
+    /// `Client::builder().requests_per_minute(5).max_retries(3).build(api_key)`.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Fetches and parses a time series for `symbol` using `function`. See
+    /// [`alpha_vantage::Client::get_time_series`] for the meaning of `interval` and
+    /// `output_size`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`crate::error::QaError`] if the request fails, Alpha Vantage reports an
+    /// in-body error, or the response cannot be parsed.
+    pub fn get_time_series(
+        &self,
+        function: AlphaVantageRangeFunction,
+        symbol: impl Into<String>,
+        interval: impl Into<String>,
+        output_size: impl Into<String>,
+    ) -> Result<HistoricalData> {
+        let symbol = symbol.into();
+        let interval = interval.into();
+        let output_size = output_size.into();
+
+        let url = alpha_vantage::time_series_url(&function, &symbol, &self.api_key, &interval, &output_size);
+        let body = self.get_raw(&url)?;
+
+        alpha_vantage::parse_historical(&body, &function)
+    }
+
+    /// Sends a GET request to `url`, waiting for a throttle slot first and retrying with
+    /// exponential backoff if Alpha Vantage reports a rate limit, up to the configured
+    /// `max_retries`. Other in-body errors are left for the caller's parser to detect.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QaError::Network`] if the request fails, or a [`QaError::RateLimited`]
+    /// if Alpha Vantage is still rate-limiting the client after all retries are exhausted.
+    fn get_raw(&self, url: &str) -> Result<String> {
+        let mut attempt = 0;
+        loop {
+            self.throttle.wait_for_slot_blocking();
+
+            let body = self.http.get(url)?;
+
+            match alpha_vantage::api_error_in(&body) {
+                Some(QaError::RateLimited { .. }) if attempt < self.throttle.max_retries() => {
+                    attempt += 1;
+                    std::thread::sleep(Throttle::backoff(attempt));
+                }
+                Some(err @ QaError::RateLimited { .. }) => return Err(err.into()),
+                _ => return Ok(body),
+            }
+        }
+    }
+}
+
+/// Configures and builds a [`Client`], for callers that need rate-limit throttling or
+/// retry behavior.
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    requests_per_minute: Option<u32>,
+    max_retries: u32,
+}
+
+impl ClientBuilder {
+    /// Caps the client to at most `limit` requests per rolling 60-second window,
+    /// delaying calls that would exceed it instead of letting them fail. A `limit` of `0`
+    /// sets no ceiling.
+    pub fn requests_per_minute(mut self, limit: u32) -> Self {
+        self.requests_per_minute = Some(limit);
+        self
+    }
+
+    /// Sets how many times a rate-limited request is retried, with exponential backoff,
+    /// before giving up with a [`QaError::RateLimited`]. Defaults to `0` (no retries).
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Builds the client, authenticated with `api_key`, using the default
+    /// `reqwest`-backed transport.
+    pub fn build(self, api_key: impl Into<String>) -> Client {
+        self.build_with_request_client(api_key, ReqwestRequestClient::new())
+    }
+
+    /// Builds the client, authenticated with `api_key`, using a custom [`RequestClient`]
+    /// backend.
+    pub fn build_with_request_client(self, api_key: impl Into<String>, http: impl RequestClient + 'static) -> Client {
+        Client {
+            api_key: api_key.into(),
+            http: Box::new(http),
+            throttle: Throttle::new(self.requests_per_minute, self.max_retries),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use std::fmt;
+
+/// Errors that can occur when querying or parsing third-party market data APIs.
+///
+/// Alpha Vantage, for example, returns HTTP 200 even on failure, embedding an
+/// `"Error Message"`, `"Note"`, or `"Information"` key in the JSON body instead. These
+/// variants let callers distinguish a transient rate limit (retryable) from a permanent
+/// bad request by downcasting the returned `anyhow::Error` with `downcast_ref`.
+#[derive(Debug)]
+pub enum QaError {
+    /// The underlying HTTP request failed (e.g. connection refused, timeout, non-success status).
+    Network(String),
+    /// The API rejected the query outright, e.g. an unknown symbol or function.
+    Api { message: String },
+    /// The API's rate limit was exceeded (for Alpha Vantage's free tier: 5 requests/minute, 25/day).
+    RateLimited { note: String },
+    /// The requested function requires a premium subscription.
+    Premium { message: String },
+    /// The response body did not have the expected shape.
+    Parse(String),
+}
+
+impl fmt::Display for QaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QaError::Network(message) => write!(f, "network error: {message}"),
+            QaError::Api { message } => write!(f, "API error: {message}"),
+            QaError::RateLimited { note } => write!(f, "rate limit exceeded: {note}"),
+            QaError::Premium { message } => write!(f, "premium-only endpoint: {message}"),
+            QaError::Parse(message) => write!(f, "failed to parse response: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for QaError {}
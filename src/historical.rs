@@ -1,25 +1,28 @@
 use std::vec::Vec;
 use chrono::DateTime;
 
+#[derive(Debug)]
 pub struct HistoricalMetaData {
-    information: String,
-    symbol: String,
-    last_refreshed: String,
-    interval: String,
-    output_size: String,
-    time_zone: String,
+    pub information: String,
+    pub symbol: String,
+    pub last_refreshed: String,
+    pub interval: String,
+    pub output_size: String,
+    pub time_zone: String,
 }
 
+#[derive(Debug)]
 pub struct HistoricalPrice {
-    time: DateTime<chrono::Utc>,
-    open: f64,
-    high: f64,
-    low: f64,
-    close: f64,
-    volume: i32,
+    pub time: DateTime<chrono::Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: i32,
 }
 
+#[derive(Debug)]
 pub struct HistoricalData {
-    meta_data: HistoricalMetaData,
-    time_series: Vec<HistoricalPrice>,
-}
\ No newline at end of file
+    pub meta_data: HistoricalMetaData,
+    pub time_series: Vec<HistoricalPrice>,
+}
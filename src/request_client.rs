@@ -0,0 +1,46 @@
+#![cfg(feature = "blocking")]
+
+use std::fmt::Debug;
+use anyhow::Result;
+
+use crate::error::QaError;
+
+/// Abstracts the HTTP transport used to fetch the body of a GET request as text.
+///
+/// Implementing this trait lets callers swap the default `reqwest`-backed transport for
+/// another HTTP client (e.g. `ureq`) or a mock, without touching the rest of the crate.
+/// This is what the `blocking` feature's [`Client`](crate::blocking::Client) is built on.
+pub trait RequestClient: Debug + Send + Sync {
+    /// Performs a GET request against `url` and returns the response body as a string.
+    fn get(&self, url: &str) -> Result<String>;
+}
+
+/// The default [`RequestClient`], backed by a reused [`reqwest::blocking::Client`].
+#[derive(Debug, Default)]
+pub struct ReqwestRequestClient {
+    http: reqwest::blocking::Client,
+}
+
+impl ReqwestRequestClient {
+    /// Creates a new backend with its own connection pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RequestClient for ReqwestRequestClient {
+    fn get(&self, url: &str) -> Result<String> {
+        let response = self
+            .http
+            .get(url)
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status)
+            .map_err(|e| QaError::Network(format!("failed to send request to {url}: {e}")))?;
+
+        let body = response
+            .text()
+            .map_err(|e| QaError::Network(format!("failed to get response text from {url}: {e}")))?;
+
+        Ok(body)
+    }
+}
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+/// Deserializes a numeric value that Alpha Vantage has encoded as a JSON string (its
+/// numbered response fields, e.g. `"1. open"` or `"9. matchScore"`, are all strings
+/// regardless of the underlying type).
+pub(crate) fn str_as_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    raw.parse().map_err(serde::de::Error::custom)
+}
+
+/// Same as [`str_as_f64`], but for integer fields (e.g. `"5. volume"`).
+pub(crate) fn str_as_i32<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    raw.parse().map_err(serde::de::Error::custom)
+}
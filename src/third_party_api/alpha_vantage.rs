@@ -1,6 +1,17 @@
+use std::collections::HashMap;
 use std::fmt::{self, Debug};
+use std::sync::Arc;
 use anyhow::{Result, Context};
-use reqwest::Client;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::QaError;
+use crate::historical::{HistoricalData, HistoricalMetaData, HistoricalPrice};
+use crate::serde_util::{str_as_f64, str_as_i32};
+use crate::third_party_api::symbol_search::{self, SymbolMatch};
+use crate::third_party_api::throttle::Throttle;
 
 /// Specifies the time range function to be called in the Alpha Vantage API
 #[derive(Debug)]
@@ -27,73 +38,632 @@ impl fmt::Display for AlphaVantageRangeFunction {
     }
 }
 
-/// Specifies the parameters for a query to the Alpha Vantage API
+/// Specifies the parameters for a time-series query against the Alpha Vantage API.
+///
+/// The API key is supplied separately by the [`Client`] issuing the query, rather than
+/// being a field here.
 #[derive(Debug)]
-pub struct AlphaVantageRangeQuery {
+struct AlphaVantageRangeQuery {
     /// The function to be called in the Alpha Vantage API
     function: AlphaVantageRangeFunction,
     /// The symbol of the equity to be queried
     symbol: String,
-    /// The API key to be used in the query
-    api_key: String,
     /// The interval between two consecutive data points in the time series
     interval: String,
     /// The size of the output time series of the query
     output_size: String,
 }
 
-/// Sends a query to the AlphaVantage API and retrieves the response as a string.
-///
-/// This function constructs a URL based on the provided `AlphaVantageRangeQuery` and sends an HTTP GET request to that URL.
-/// It expects the query to include the necessary function, symbol, API key, interval, and output size parameters.
-///
-/// # Arguments
-///
-/// * `query` - An `AlphaVantageRangeQuery` struct containing the query parameters.
-///
-/// # Returns
-///
-/// * If successful, it returns a `Result<String, Error>` where `String` contains the response from the API.
-/// * If an error occurs during the request or response handling, it returns an `Err` variant with an error message.
+/// A client for the Alpha Vantage API.
 ///
-/// # Example
+/// Holds the API key and a [`reqwest::Client`] that is reused across requests, so that
+/// repeated calls share a connection pool instead of paying for a fresh TCP/TLS handshake
+/// every time, the way constructing a `reqwest::Client` per call would.
+#[derive(Debug, Clone)]
+pub struct Client {
+    api_key: String,
+    http: reqwest::Client,
+    throttle: Arc<Throttle>,
+}
+
+impl Client {
+    /// Creates a new client for the Alpha Vantage API authenticated with `api_key`, with
+    /// no rate-limit throttling or retries. Use [`Client::builder`] to configure those.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Client::builder().build(api_key)
+    }
+
+    /// Starts building a client with rate-limit throttling and/or retry behavior, e.g.
+    /// `Client::builder().requests_per_minute(5).max_retries(3).build(api_key)`.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Fetches and parses a time series for `symbol` using `function`.
+    ///
+    /// `interval` (e.g. `"15min"`) only applies to [`AlphaVantageRangeFunction::Intraday`]
+    /// and is ignored otherwise. `output_size` is either `"compact"` (the latest 100 data
+    /// points) or `"full"` (the entire history Alpha Vantage has for the symbol).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QaError`] if the request fails, Alpha Vantage reports an in-body error,
+    /// or the response cannot be parsed.
+    pub async fn get_time_series(
+        &self,
+        function: AlphaVantageRangeFunction,
+        symbol: impl Into<String>,
+        interval: impl Into<String>,
+        output_size: impl Into<String>,
+    ) -> Result<HistoricalData> {
+        let query = AlphaVantageRangeQuery {
+            function,
+            symbol: symbol.into(),
+            interval: interval.into(),
+            output_size: output_size.into(),
+        };
+
+        let body = self.query(&query).await?;
+        parse_historical(&body, &query.function)
+    }
+
+    /// Resolves free-text `keywords` (e.g. a company name) to tradable symbols via the
+    /// `SYMBOL_SEARCH` function, for use with [`Client::get_time_series`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QaError`] if the request fails, Alpha Vantage reports an in-body error,
+    /// or the response cannot be parsed.
+    pub async fn search(&self, keywords: impl AsRef<str>) -> Result<Vec<SymbolMatch>> {
+        let url = search_url(keywords.as_ref(), &self.api_key);
+
+        let body = self.get_raw(url).await?;
+        symbol_search::parse_symbol_matches(&body)
+    }
+
+    /// Sends `query` to the Alpha Vantage API and retrieves the response as a string.
+    /// A rate limit that outlasts the retries is returned as an error; other in-body errors
+    /// are left for [`parse_historical`] to detect.
+    async fn query(&self, query: &AlphaVantageRangeQuery) -> Result<String> {
+        let url = time_series_url(
+            &query.function,
+            &query.symbol,
+            &self.api_key,
+            &query.interval,
+            &query.output_size,
+        );
+
+        self.get_raw(url).await
+    }
+
+    /// The API key this client was created with, for other modules that build their own
+    /// query URLs (e.g. currency/FX queries).
+    pub(crate) fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// Sends a GET request to `url`, waiting for a throttle slot first and retrying with
+    /// exponential backoff if Alpha Vantage reports a rate limit, up to the configured
+    /// `max_retries`. Other in-body errors are left for the caller's parser to detect.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QaError::Network`] if the request fails, or a [`QaError::RateLimited`]
+    /// if Alpha Vantage is still rate-limiting the client after all retries are exhausted.
+    pub(crate) async fn get_raw(&self, url: String) -> Result<String> {
+        let mut attempt = 0;
+        loop {
+            self.throttle.wait_for_slot().await;
+
+            let response = self
+                .http
+                .get(&url)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| QaError::Network(format!("failed to send request to {url}: {e}")))?;
+
+            let body = response
+                .text()
+                .await
+                .map_err(|e| QaError::Network(format!("failed to get response text from {url}: {e}")))?;
+
+            match api_error_in(&body) {
+                Some(QaError::RateLimited { .. }) if attempt < self.throttle.max_retries() => {
+                    attempt += 1;
+                    tokio::time::sleep(Throttle::backoff(attempt)).await;
+                }
+                Some(err @ QaError::RateLimited { .. }) => return Err(err.into()),
+                _ => return Ok(body),
+            }
+        }
+    }
+}
+
+/// Configures and builds a [`Client`], for callers that need rate-limit throttling or
+/// retry behavior.
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    requests_per_minute: Option<u32>,
+    max_retries: u32,
+}
+
+impl ClientBuilder {
+    /// Caps the client to at most `limit` requests per rolling 60-second window,
+    /// delaying calls that would exceed it instead of letting them fail. A `limit` of `0`
+    /// sets no ceiling.
+    pub fn requests_per_minute(mut self, limit: u32) -> Self {
+        self.requests_per_minute = Some(limit);
+        self
+    }
+
+    /// Sets how many times a rate-limited request is retried, with exponential backoff,
+    /// before giving up with a [`QaError::RateLimited`]. Defaults to `0` (no retries).
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Builds the client, authenticated with `api_key`.
+    pub fn build(self, api_key: impl Into<String>) -> Client {
+        Client {
+            api_key: api_key.into(),
+            http: reqwest::Client::new(),
+            throttle: Arc::new(Throttle::new(self.requests_per_minute, self.max_retries)),
+        }
+    }
+}
+
+/// Builds the URL for a time-series query against the Alpha Vantage API. Shared by the
+/// async [`Client`] and the `blocking` feature's client so both construct requests the
+/// same way.
+pub(crate) fn time_series_url(
+    function: &AlphaVantageRangeFunction,
+    symbol: &str,
+    api_key: &str,
+    interval: &str,
+    output_size: &str,
+) -> String {
+    format!(
+        "https://www.alphavantage.co/query?function={function}&symbol={symbol}&apikey={api_key}&interval={interval}&outputsize={output_size}",
+    )
+}
+
+/// Builds the URL for a `SYMBOL_SEARCH` query against the Alpha Vantage API.
 ///
-/// ```rust
-/// use alphavantage::AlphaVantageRangeQuery;
+/// `keywords` is free text (e.g. a company name) and is percent-encoded before being
+/// interpolated, since unencoded `&` (as in "AT&T" or "Johnson & Johnson") would otherwise
+/// be parsed as a query-parameter separator and silently truncate the search term.
+pub(crate) fn search_url(keywords: &str, api_key: &str) -> String {
+    format!(
+        "https://www.alphavantage.co/query?function=SYMBOL_SEARCH&keywords={}&apikey={api_key}",
+        utf8_percent_encode(keywords, NON_ALPHANUMERIC),
+    )
+}
+
+/// Checks an Alpha Vantage response body for its in-body error conventions: an
+/// `"Error Message"` (bad symbol/function), a `"Note"` (rate limit exceeded), or an
+/// `"Information"` (premium-only endpoint) key. Alpha Vantage reports all three with an
+/// HTTP 200 status, so a successful request still needs this check before its body is
+/// treated as valid data.
+pub(crate) fn api_error_in(body: &str) -> Option<QaError> {
+    let object = serde_json::from_str::<Value>(body).ok()?;
+    let object = object.as_object()?;
+
+    if let Some(message) = object.get("Error Message").and_then(Value::as_str) {
+        return Some(QaError::Api { message: message.to_string() });
+    }
+    if let Some(note) = object.get("Note").and_then(Value::as_str) {
+        return Some(QaError::RateLimited { note: note.to_string() });
+    }
+    if let Some(message) = object.get("Information").and_then(Value::as_str) {
+        return Some(QaError::Premium { message: message.to_string() });
+    }
+
+    None
+}
+
+/// A single entry of an unadjusted Alpha Vantage time series (`Intraday`, `Daily`,
+/// `Weekly`, `Monthly`), keyed by its numbered field names (`"1. open"`, `"2. high"`, ...
+/// `"5. volume"`). The values are transmitted as strings and need to be parsed into their
+/// numeric types.
+#[derive(Debug, Deserialize)]
+struct RawPriceEntry {
+    #[serde(rename = "1. open", deserialize_with = "str_as_f64")]
+    open: f64,
+    #[serde(rename = "2. high", deserialize_with = "str_as_f64")]
+    high: f64,
+    #[serde(rename = "3. low", deserialize_with = "str_as_f64")]
+    low: f64,
+    #[serde(rename = "4. close", deserialize_with = "str_as_f64")]
+    close: f64,
+    #[serde(rename = "5. volume", deserialize_with = "str_as_i32")]
+    volume: i32,
+}
+
+/// A single entry of an *adjusted* Alpha Vantage time series (`DailyAdjusted`,
+/// `WeeklyAdjusted`, `MonthlyAdjusted`). These insert `"5. adjusted close"` before the
+/// volume field, which shifts volume to `"6. volume"`, and append a dividend amount (and,
+/// for `DailyAdjusted` only, a split coefficient) that `HistoricalPrice` has no field for
+/// and that serde silently ignores.
+#[derive(Debug, Deserialize)]
+struct RawAdjustedPriceEntry {
+    #[serde(rename = "1. open", deserialize_with = "str_as_f64")]
+    open: f64,
+    #[serde(rename = "2. high", deserialize_with = "str_as_f64")]
+    high: f64,
+    #[serde(rename = "3. low", deserialize_with = "str_as_f64")]
+    low: f64,
+    #[serde(rename = "4. close", deserialize_with = "str_as_f64")]
+    close: f64,
+    #[serde(rename = "6. volume", deserialize_with = "str_as_i32")]
+    volume: i32,
+}
+
+/// Looks up the value for a "Meta Data" field regardless of its numeric prefix, since
+/// Alpha Vantage renumbers these keys depending on which fields a given function reports
+/// (e.g. `"4. Interval"` only appears for intraday queries).
+pub(crate) fn meta_field(meta: &HashMap<String, String>, suffix: &str) -> Option<String> {
+    meta.iter()
+        .find(|(key, _)| key.ends_with(suffix))
+        .map(|(_, value)| value.clone())
+}
+
+/// Determines the name of the time series object in an Alpha Vantage response body for
+/// the given function. Most functions use a fixed name, but intraday responses embed the
+/// requested interval in the key (e.g. `"Time Series (15min)"`), so for that case we fall
+/// back to scanning the body for the first key with the expected prefix.
+fn time_series_key<'a>(function: &AlphaVantageRangeFunction, body: &'a Value) -> Result<&'a str> {
+    use AlphaVantageRangeFunction::*;
+
+    let fixed_key = match function {
+        Daily | DailyAdjusted => Some("Time Series (Daily)"),
+        Weekly => Some("Weekly Time Series"),
+        WeeklyAdjusted => Some("Weekly Adjusted Time Series"),
+        Monthly => Some("Monthly Time Series"),
+        MonthlyAdjusted => Some("Monthly Adjusted Time Series"),
+        Intraday => None,
+    };
+
+    let object = body
+        .as_object()
+        .context("expected the response body to be a JSON object")?;
+
+    if let Some(key) = fixed_key {
+        if object.contains_key(key) {
+            return Ok(key);
+        }
+    }
+
+    object
+        .keys()
+        .find(|key| key.starts_with("Time Series ("))
+        .map(|key| key.as_str())
+        .ok_or_else(|| QaError::Parse(format!("could not find a time series object for function {function:?}")).into())
+}
+
+/// Parses an Alpha Vantage timestamp, which is a bare date (`"2024-01-01"`) for daily,
+/// weekly and monthly series, or a full date and time (`"2024-01-01 15:30:00"`) for
+/// intraday series.
+pub(crate) fn parse_timestamp(raw: &str) -> Result<DateTime<Utc>> {
+    if let Ok(naive) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S") {
+        return Ok(Utc.from_utc_datetime(&naive));
+    }
+
+    let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map_err(|e| QaError::Parse(format!("could not parse timestamp `{raw}`: {e}")))?;
+
+    Ok(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()))
+}
+
+/// Parses a time series object's entries into [`HistoricalPrice`]s, using the raw-entry
+/// shape appropriate for `function`: [`RawAdjustedPriceEntry`] for the `*Adjusted`
+/// variants, whose volume field is shifted by the extra `"5. adjusted close"` field, and
+/// [`RawPriceEntry`] otherwise.
+fn parse_price_entries(
+    raw_series: &Value,
+    function: &AlphaVantageRangeFunction,
+    series_key: &str,
+) -> Result<Vec<HistoricalPrice>> {
+    use AlphaVantageRangeFunction::*;
+
+    match function {
+        DailyAdjusted | WeeklyAdjusted | MonthlyAdjusted => {
+            let raw_series: HashMap<String, RawAdjustedPriceEntry> = serde_json::from_value(raw_series.clone())
+                .map_err(|e| QaError::Parse(format!("failed to parse \"{series_key}\" object: {e}")))?;
+            raw_series
+                .into_iter()
+                .map(|(timestamp, entry)| {
+                    Ok(HistoricalPrice {
+                        time: parse_timestamp(&timestamp)?,
+                        open: entry.open,
+                        high: entry.high,
+                        low: entry.low,
+                        close: entry.close,
+                        volume: entry.volume,
+                    })
+                })
+                .collect()
+        }
+        Intraday | Daily | Weekly | Monthly => {
+            let raw_series: HashMap<String, RawPriceEntry> = serde_json::from_value(raw_series.clone())
+                .map_err(|e| QaError::Parse(format!("failed to parse \"{series_key}\" object: {e}")))?;
+            raw_series
+                .into_iter()
+                .map(|(timestamp, entry)| {
+                    Ok(HistoricalPrice {
+                        time: parse_timestamp(&timestamp)?,
+                        open: entry.open,
+                        high: entry.high,
+                        low: entry.low,
+                        close: entry.close,
+                        volume: entry.volume,
+                    })
+                })
+                .collect()
+        }
+    }
+}
+
+/// Parses a raw Alpha Vantage time series response body into a [`HistoricalData`].
 ///
-/// let query = AlphaVantageRangeQuery {
-///     function: "TIME_SERIES_INTRADAY",
-///     symbol: "AAPL",
-///     api_key: "your_api_key",
-///     interval: "15min",
-///     output_size: "compact",
-/// };
+/// Alpha Vantage nests a `"Meta Data"` object alongside a time series object whose key
+/// name varies by `function` (e.g. `"Time Series (Daily)"`, `"Weekly Time Series"`), and
+/// each entry in that object uses numbered string keys (`"1. open"` ... `"5. volume"`, or
+/// `"6. volume"` for the `*Adjusted` variants) whose values need to be parsed into
+/// `f64`/`i32`.
 ///
-/// match query(query) {
-///     Ok(response) => println!("Response: {}", response),
-///     Err(err) => eprintln!("Error: {}", err),
-/// }
-/// ```
-/// 
 /// # Errors
 ///
-/// This function may return an error if:
-/// - The HTTP request fails to be sent.
-/// - The response status code is not successful
-/// - The response body cannot be converted to a string.
-pub async fn query(query: AlphaVantageRangeQuery) -> Result<String> {
-    let url = format!(
-        "https://www.alphavantage.co/query?function={}&symbol={}&apikey={}&interval={}&outputsize={}",
-        query.function,
-        query.symbol,
-        query.api_key,
-        query.interval,
-        query.output_size,
-    );
-
-    Client::new()
-        .get(url).send()
-        .await.context(format!("Failed to send request for query {:?}", query))?
-        .text()
-        .await.context(format!("Failed to get response text for query {:?}", query))
-}
\ No newline at end of file
+/// Returns a [`QaError::Api`], [`QaError::RateLimited`], or [`QaError::Premium`] if Alpha
+/// Vantage reported an in-body error instead of data, a [`QaError::Parse`] if `body` is not
+/// valid JSON or does not have the expected shape, or a [`QaError::Parse`] if any field
+/// within it fails to parse.
+pub fn parse_historical(body: &str, function: &AlphaVantageRangeFunction) -> Result<HistoricalData> {
+    if let Some(err) = api_error_in(body) {
+        return Err(err.into());
+    }
+
+    let value: Value = serde_json::from_str(body)
+        .map_err(|e| QaError::Parse(format!("response body was not valid JSON: {e}")))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| QaError::Parse("expected the response body to be a JSON object".to_string()))?;
+
+    let raw_meta = object
+        .get("Meta Data")
+        .ok_or_else(|| QaError::Parse("response is missing a \"Meta Data\" object".to_string()))?;
+    let raw_meta: HashMap<String, String> = serde_json::from_value(raw_meta.clone())
+        .map_err(|e| QaError::Parse(format!("failed to parse \"Meta Data\" object: {e}")))?;
+
+    let series_key = time_series_key(function, &value)?;
+    let raw_series = object
+        .get(series_key)
+        .ok_or_else(|| QaError::Parse(format!("response is missing the \"{series_key}\" object")))?;
+
+    let meta_data = HistoricalMetaData {
+        information: meta_field(&raw_meta, "Information").unwrap_or_default(),
+        symbol: meta_field(&raw_meta, "Symbol").unwrap_or_default(),
+        last_refreshed: meta_field(&raw_meta, "Last Refreshed").unwrap_or_default(),
+        interval: meta_field(&raw_meta, "Interval").unwrap_or_default(),
+        output_size: meta_field(&raw_meta, "Output Size").unwrap_or_default(),
+        time_zone: meta_field(&raw_meta, "Time Zone").unwrap_or_default(),
+    };
+
+    let mut time_series = parse_price_entries(raw_series, function, series_key)?;
+    time_series.sort_by(|a, b| a.time.cmp(&b.time));
+
+    if time_series.is_empty() {
+        return Err(QaError::Parse(format!("\"{series_key}\" object in response did not contain any entries")).into());
+    }
+
+    Ok(HistoricalData {
+        meta_data,
+        time_series,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_url_percent_encodes_keywords_with_special_characters() {
+        let url = search_url("AT&T", "demo");
+        assert!(
+            url.contains("keywords=AT%26T"),
+            "expected keywords to be percent-encoded, got: {url}"
+        );
+        assert!(!url.contains("keywords=AT&T"), "unencoded \"&\" would truncate the search term: {url}");
+    }
+
+    fn daily_body() -> String {
+        r#"{
+            "Meta Data": {
+                "1. Information": "Daily Prices",
+                "2. Symbol": "IBM",
+                "3. Last Refreshed": "2024-01-02",
+                "4. Output Size": "Compact",
+                "5. Time Zone": "US/Eastern"
+            },
+            "Time Series (Daily)": {
+                "2024-01-02": {
+                    "1. open": "100.0",
+                    "2. high": "101.0",
+                    "3. low": "99.0",
+                    "4. close": "100.5",
+                    "5. volume": "1000"
+                }
+            }
+        }"#
+        .to_string()
+    }
+
+    fn weekly_body() -> String {
+        r#"{
+            "Meta Data": {
+                "1. Information": "Weekly Prices",
+                "2. Symbol": "IBM",
+                "3. Last Refreshed": "2024-01-02",
+                "4. Time Zone": "US/Eastern"
+            },
+            "Weekly Time Series": {
+                "2024-01-02": {
+                    "1. open": "100.0",
+                    "2. high": "101.0",
+                    "3. low": "99.0",
+                    "4. close": "100.5",
+                    "5. volume": "1000"
+                }
+            }
+        }"#
+        .to_string()
+    }
+
+    fn monthly_body() -> String {
+        r#"{
+            "Meta Data": {
+                "1. Information": "Monthly Prices",
+                "2. Symbol": "IBM",
+                "3. Last Refreshed": "2024-01-02",
+                "4. Time Zone": "US/Eastern"
+            },
+            "Monthly Time Series": {
+                "2024-01-02": {
+                    "1. open": "100.0",
+                    "2. high": "101.0",
+                    "3. low": "99.0",
+                    "4. close": "100.5",
+                    "5. volume": "1000"
+                }
+            }
+        }"#
+        .to_string()
+    }
+
+    fn intraday_body() -> String {
+        r#"{
+            "Meta Data": {
+                "1. Information": "Intraday Prices",
+                "2. Symbol": "IBM",
+                "3. Last Refreshed": "2024-01-02 15:30:00",
+                "4. Interval": "15min",
+                "5. Output Size": "Compact",
+                "6. Time Zone": "US/Eastern"
+            },
+            "Time Series (15min)": {
+                "2024-01-02 15:30:00": {
+                    "1. open": "100.0",
+                    "2. high": "101.0",
+                    "3. low": "99.0",
+                    "4. close": "100.5",
+                    "5. volume": "1000"
+                }
+            }
+        }"#
+        .to_string()
+    }
+
+    fn adjusted_body(series_key: &str) -> String {
+        format!(
+            r#"{{
+                "Meta Data": {{
+                    "1. Information": "Adjusted Prices",
+                    "2. Symbol": "IBM",
+                    "3. Last Refreshed": "2024-01-02",
+                    "4. Time Zone": "US/Eastern"
+                }},
+                "{series_key}": {{
+                    "2024-01-02": {{
+                        "1. open": "100.0",
+                        "2. high": "101.0",
+                        "3. low": "99.0",
+                        "4. close": "100.5",
+                        "5. adjusted close": "100.5",
+                        "6. volume": "1000",
+                        "7. dividend amount": "0.0",
+                        "8. split coefficient": "1.0"
+                    }}
+                }}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn parses_daily_time_series() {
+        let data = parse_historical(&daily_body(), &AlphaVantageRangeFunction::Daily).unwrap();
+        assert_eq!(data.time_series.len(), 1);
+        assert_eq!(data.time_series[0].volume, 1000);
+    }
+
+    #[test]
+    fn parses_weekly_time_series() {
+        let data = parse_historical(&weekly_body(), &AlphaVantageRangeFunction::Weekly).unwrap();
+        assert_eq!(data.time_series[0].volume, 1000);
+    }
+
+    #[test]
+    fn parses_monthly_time_series() {
+        let data = parse_historical(&monthly_body(), &AlphaVantageRangeFunction::Monthly).unwrap();
+        assert_eq!(data.time_series[0].volume, 1000);
+    }
+
+    #[test]
+    fn parses_intraday_time_series() {
+        let data = parse_historical(&intraday_body(), &AlphaVantageRangeFunction::Intraday).unwrap();
+        assert_eq!(data.time_series[0].volume, 1000);
+    }
+
+    #[test]
+    fn parses_daily_adjusted_time_series() {
+        let body = adjusted_body("Time Series (Daily)");
+        let data = parse_historical(&body, &AlphaVantageRangeFunction::DailyAdjusted).unwrap();
+        assert_eq!(data.time_series[0].close, 100.5);
+        assert_eq!(data.time_series[0].volume, 1000);
+    }
+
+    #[test]
+    fn parses_weekly_adjusted_time_series() {
+        let body = adjusted_body("Weekly Adjusted Time Series");
+        let data = parse_historical(&body, &AlphaVantageRangeFunction::WeeklyAdjusted).unwrap();
+        assert_eq!(data.time_series[0].volume, 1000);
+    }
+
+    #[test]
+    fn parses_monthly_adjusted_time_series() {
+        let body = adjusted_body("Monthly Adjusted Time Series");
+        let data = parse_historical(&body, &AlphaVantageRangeFunction::MonthlyAdjusted).unwrap();
+        assert_eq!(data.time_series[0].volume, 1000);
+    }
+
+    #[test]
+    fn api_error_in_detects_rate_limit_note() {
+        let body = r#"{"Note": "Thank you for using Alpha Vantage! Our standard API call frequency is 5 calls per minute."}"#;
+        assert!(matches!(api_error_in(body), Some(QaError::RateLimited { .. })));
+    }
+
+    #[test]
+    fn api_error_in_detects_error_message() {
+        let body = r#"{"Error Message": "Invalid API call."}"#;
+        assert!(matches!(api_error_in(body), Some(QaError::Api { .. })));
+    }
+
+    #[test]
+    fn api_error_in_returns_none_for_valid_body() {
+        assert!(api_error_in(&daily_body()).is_none());
+    }
+
+    #[test]
+    fn meta_field_matches_by_suffix_regardless_of_numbering() {
+        let mut meta = HashMap::new();
+        meta.insert("4. Interval".to_string(), "15min".to_string());
+        assert_eq!(meta_field(&meta, "Interval").as_deref(), Some("15min"));
+    }
+
+    #[test]
+    fn parse_timestamp_parses_date_only_and_date_time() {
+        assert!(parse_timestamp("2024-01-02").is_ok());
+        assert!(parse_timestamp("2024-01-02 15:30:00").is_ok());
+        assert!(parse_timestamp("not-a-date").is_err());
+    }
+}
@@ -0,0 +1,421 @@
+use std::collections::HashMap;
+use std::fmt;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::QaError;
+use crate::serde_util::str_as_f64;
+use crate::third_party_api::alpha_vantage::{api_error_in, meta_field, parse_timestamp, Client};
+
+/// Specifies the currency function to be called in the Alpha Vantage API. These use
+/// different URL parameters (`from_currency`/`to_currency` for `ExchangeRate`,
+/// `symbol`/`market` for `DigitalCurrencyDaily`) and different response shapes than the
+/// equity-only [`AlphaVantageRangeFunction`](crate::third_party_api::alpha_vantage::AlphaVantageRangeFunction).
+///
+/// FX time series functions are not part of this enum; see [`FxFunction`].
+#[derive(Debug)]
+pub enum CurrencyFunction {
+    /// The realtime exchange rate between two physical currencies, or a physical and digital currency.
+    ExchangeRate,
+    /// A daily time series for a digital/crypto currency, valued in a market currency.
+    DigitalCurrencyDaily,
+}
+
+impl fmt::Display for CurrencyFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CurrencyFunction::ExchangeRate => "CURRENCY_EXCHANGE_RATE",
+            CurrencyFunction::DigitalCurrencyDaily => "DIGITAL_CURRENCY_DAILY",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Specifies the FX function to be called by [`Client::get_fx_time_series`]. A dedicated
+/// enum scoped to just these two functions, rather than a parameter of [`CurrencyFunction`],
+/// so that passing `ExchangeRate` or `DigitalCurrencyDaily` there is a compile error instead
+/// of a malformed query that only fails at runtime.
+#[derive(Debug)]
+pub enum FxFunction {
+    /// An intraday time series for a physical currency pair.
+    Intraday,
+    /// A daily time series for a physical currency pair, covering 20+ years of historical data.
+    Daily,
+}
+
+impl fmt::Display for FxFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FxFunction::Intraday => "FX_INTRADAY",
+            FxFunction::Daily => "FX_DAILY",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The realtime exchange rate between two currencies, as returned by
+/// `CURRENCY_EXCHANGE_RATE`.
+#[derive(Debug, Clone)]
+pub struct ExchangeRate {
+    pub from: String,
+    pub to: String,
+    pub rate: f64,
+    pub last_refreshed: String,
+    pub time_zone: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawExchangeRate {
+    #[serde(rename = "1. From_Currency Code")]
+    from: String,
+    #[serde(rename = "3. To_Currency Code")]
+    to: String,
+    #[serde(rename = "5. Exchange Rate", deserialize_with = "str_as_f64")]
+    rate: f64,
+    #[serde(rename = "6. Last Refreshed")]
+    last_refreshed: String,
+    #[serde(rename = "7. Time Zone")]
+    time_zone: String,
+}
+
+/// A single entry of an FX or digital-currency time series. Unlike equity time series,
+/// Alpha Vantage does not report a volume for these.
+#[derive(Debug)]
+pub struct CurrencyPrice {
+    pub time: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+/// A parsed FX or digital-currency time series, as returned by `FX_INTRADAY`, `FX_DAILY`,
+/// or `DIGITAL_CURRENCY_DAILY`.
+#[derive(Debug)]
+pub struct CurrencyTimeSeries {
+    pub time_zone: String,
+    pub prices: Vec<CurrencyPrice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCurrencyPriceEntry {
+    #[serde(rename = "1. open", deserialize_with = "str_as_f64")]
+    open: f64,
+    #[serde(rename = "2. high", deserialize_with = "str_as_f64")]
+    high: f64,
+    #[serde(rename = "3. low", deserialize_with = "str_as_f64")]
+    low: f64,
+    #[serde(rename = "4. close", deserialize_with = "str_as_f64")]
+    close: f64,
+}
+
+/// Looks up a `DIGITAL_CURRENCY_DAILY` entry's numeric field by its key prefix (e.g.
+/// `"1a."` for the market-currency open). Unlike plain FX entries, each OHLC value here is
+/// reported twice — once in the requested market currency (e.g. `"1a. open (EUR)"`) and
+/// once in USD (e.g. `"1b. open (USD)"`) — and the market currency's name is embedded in
+/// the key suffix, so it can't be matched with a fixed `#[serde(rename = ...)]` the way
+/// [`RawCurrencyPriceEntry`] is.
+fn digital_currency_field(entry: &serde_json::Map<String, Value>, prefix: &str) -> Result<f64> {
+    let raw = entry
+        .iter()
+        .find(|(key, _)| key.starts_with(prefix))
+        .map(|(_, value)| value)
+        .ok_or_else(|| QaError::Parse(format!("entry is missing a \"{prefix}\" field")))?;
+    let raw = raw
+        .as_str()
+        .ok_or_else(|| QaError::Parse(format!("\"{prefix}\" field was not a string")))?;
+
+    raw.parse::<f64>()
+        .map_err(|e| QaError::Parse(format!("failed to parse \"{prefix}\" field: {e}")).into())
+}
+
+/// Parses a `CURRENCY_EXCHANGE_RATE` response body.
+///
+/// # Errors
+///
+/// Returns a [`QaError`] if Alpha Vantage reported an in-body error, if `body` is not
+/// valid JSON, or if it does not contain a `"Realtime Currency Exchange Rate"` object.
+pub fn parse_exchange_rate(body: &str) -> Result<ExchangeRate> {
+    if let Some(err) = api_error_in(body) {
+        return Err(err.into());
+    }
+
+    let value: Value = serde_json::from_str(body)
+        .map_err(|e| QaError::Parse(format!("response body was not valid JSON: {e}")))?;
+
+    let raw = value.get("Realtime Currency Exchange Rate").ok_or_else(|| {
+        QaError::Parse("response is missing a \"Realtime Currency Exchange Rate\" object".to_string())
+    })?;
+    let raw: RawExchangeRate = serde_json::from_value(raw.clone()).map_err(|e| {
+        QaError::Parse(format!("failed to parse \"Realtime Currency Exchange Rate\" object: {e}"))
+    })?;
+
+    Ok(ExchangeRate {
+        from: raw.from,
+        to: raw.to,
+        rate: raw.rate,
+        last_refreshed: raw.last_refreshed,
+        time_zone: raw.time_zone,
+    })
+}
+
+/// Extracts the `"Meta Data"` time zone and the time series object common to
+/// `FX_INTRADAY`, `FX_DAILY`, and `DIGITAL_CURRENCY_DAILY` response bodies.
+///
+/// Unlike [`parse_historical`](crate::third_party_api::alpha_vantage::parse_historical),
+/// the time series object's key is not looked up per function: the first key of the
+/// response body that starts with `"Time Series"` is used, which covers all three
+/// functions' series names.
+fn currency_time_series_object(body: &str) -> Result<(String, String, Value)> {
+    if let Some(err) = api_error_in(body) {
+        return Err(err.into());
+    }
+
+    let value: Value = serde_json::from_str(body)
+        .map_err(|e| QaError::Parse(format!("response body was not valid JSON: {e}")))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| QaError::Parse("expected the response body to be a JSON object".to_string()))?;
+
+    let raw_meta = object
+        .get("Meta Data")
+        .ok_or_else(|| QaError::Parse("response is missing a \"Meta Data\" object".to_string()))?;
+    let raw_meta: HashMap<String, String> = serde_json::from_value(raw_meta.clone())
+        .map_err(|e| QaError::Parse(format!("failed to parse \"Meta Data\" object: {e}")))?;
+    let time_zone = meta_field(&raw_meta, "Time Zone").unwrap_or_default();
+
+    let series_key = object
+        .keys()
+        .find(|key| key.starts_with("Time Series"))
+        .ok_or_else(|| QaError::Parse("response does not contain a time series object".to_string()))?
+        .clone();
+
+    let raw_series = object
+        .get(&series_key)
+        .expect("series_key was just found as a key of this object")
+        .clone();
+
+    Ok((time_zone, series_key, raw_series))
+}
+
+/// Parses an `FX_INTRADAY` or `FX_DAILY` response body.
+///
+/// # Errors
+///
+/// Returns a [`QaError`] if Alpha Vantage reported an in-body error, if `body` is not
+/// valid JSON, or if it does not contain a `"Meta Data"` object and a time series object.
+pub fn parse_currency_time_series(body: &str) -> Result<CurrencyTimeSeries> {
+    let (time_zone, series_key, raw_series) = currency_time_series_object(body)?;
+    let raw_series: HashMap<String, RawCurrencyPriceEntry> = serde_json::from_value(raw_series)
+        .map_err(|e| QaError::Parse(format!("failed to parse \"{series_key}\" object: {e}")))?;
+
+    let mut prices = raw_series
+        .into_iter()
+        .map(|(timestamp, entry)| {
+            Ok(CurrencyPrice {
+                time: parse_timestamp(&timestamp)?,
+                open: entry.open,
+                high: entry.high,
+                low: entry.low,
+                close: entry.close,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    prices.sort_by(|a, b| a.time.cmp(&b.time));
+
+    Ok(CurrencyTimeSeries { time_zone, prices })
+}
+
+/// Parses a `DIGITAL_CURRENCY_DAILY` response body.
+///
+/// Alpha Vantage reports a materially different entry shape for this function than for
+/// plain FX time series: each OHLC value is duplicated (market currency and USD), and
+/// there are separate volume and market cap fields that [`CurrencyPrice`] has no room for,
+/// so this uses [`digital_currency_field`] rather than [`RawCurrencyPriceEntry`].
+///
+/// # Errors
+///
+/// Returns a [`QaError`] if Alpha Vantage reported an in-body error, if `body` is not
+/// valid JSON, or if it does not contain a `"Meta Data"` object and a time series object.
+pub fn parse_digital_currency_time_series(body: &str) -> Result<CurrencyTimeSeries> {
+    let (time_zone, series_key, raw_series) = currency_time_series_object(body)?;
+    let raw_series = raw_series
+        .as_object()
+        .ok_or_else(|| QaError::Parse(format!("\"{series_key}\" was not a JSON object")))?;
+
+    let mut prices = raw_series
+        .iter()
+        .map(|(timestamp, entry)| {
+            let entry = entry
+                .as_object()
+                .ok_or_else(|| QaError::Parse(format!("entry \"{timestamp}\" was not a JSON object")))?;
+            Ok(CurrencyPrice {
+                time: parse_timestamp(timestamp)?,
+                open: digital_currency_field(entry, "1a.")?,
+                high: digital_currency_field(entry, "2a.")?,
+                low: digital_currency_field(entry, "3a.")?,
+                close: digital_currency_field(entry, "4a.")?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    prices.sort_by(|a, b| a.time.cmp(&b.time));
+
+    Ok(CurrencyTimeSeries { time_zone, prices })
+}
+
+impl Client {
+    /// Fetches the realtime exchange rate between `from_currency` and `to_currency`
+    /// (e.g. `"USD"`, `"BTC"`) via `CURRENCY_EXCHANGE_RATE`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QaError`] if the request fails, Alpha Vantage reports an in-body error,
+    /// or the response cannot be parsed.
+    pub async fn get_exchange_rate(
+        &self,
+        from_currency: impl AsRef<str>,
+        to_currency: impl AsRef<str>,
+    ) -> Result<ExchangeRate> {
+        let url = format!(
+            "https://www.alphavantage.co/query?function={}&from_currency={}&to_currency={}&apikey={}",
+            CurrencyFunction::ExchangeRate,
+            from_currency.as_ref(),
+            to_currency.as_ref(),
+            self.api_key(),
+        );
+
+        let body = self.get_raw(url).await?;
+        parse_exchange_rate(&body)
+    }
+
+    /// Fetches and parses an FX time series between `from_symbol` and `to_symbol` using
+    /// `function`.
+    ///
+    /// `interval` (e.g. `"5min"`) only applies to [`FxFunction::Intraday`] and is ignored
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QaError`] if the request fails, Alpha Vantage reports an in-body error,
+    /// or the response cannot be parsed.
+    pub async fn get_fx_time_series(
+        &self,
+        function: FxFunction,
+        from_symbol: impl AsRef<str>,
+        to_symbol: impl AsRef<str>,
+        interval: impl AsRef<str>,
+    ) -> Result<CurrencyTimeSeries> {
+        let url = format!(
+            "https://www.alphavantage.co/query?function={}&from_symbol={}&to_symbol={}&interval={}&apikey={}",
+            function,
+            from_symbol.as_ref(),
+            to_symbol.as_ref(),
+            interval.as_ref(),
+            self.api_key(),
+        );
+
+        let body = self.get_raw(url).await?;
+        parse_currency_time_series(&body)
+    }
+
+    /// Fetches and parses a daily digital/crypto currency time series for `symbol` (e.g.
+    /// `"BTC"`), valued in `market` (e.g. `"USD"`), via `DIGITAL_CURRENCY_DAILY`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`QaError`] if the request fails, Alpha Vantage reports an in-body error,
+    /// or the response cannot be parsed.
+    pub async fn get_digital_currency_daily(
+        &self,
+        symbol: impl AsRef<str>,
+        market: impl AsRef<str>,
+    ) -> Result<CurrencyTimeSeries> {
+        let url = format!(
+            "https://www.alphavantage.co/query?function={}&symbol={}&market={}&apikey={}",
+            CurrencyFunction::DigitalCurrencyDaily,
+            symbol.as_ref(),
+            market.as_ref(),
+            self.api_key(),
+        );
+
+        let body = self.get_raw(url).await?;
+        parse_digital_currency_time_series(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_exchange_rate() {
+        let body = r#"{
+            "Realtime Currency Exchange Rate": {
+                "1. From_Currency Code": "USD",
+                "3. To_Currency Code": "EUR",
+                "5. Exchange Rate": "0.9123",
+                "6. Last Refreshed": "2024-01-02 15:30:00",
+                "7. Time Zone": "UTC"
+            }
+        }"#;
+        let rate = parse_exchange_rate(body).unwrap();
+        assert_eq!(rate.from, "USD");
+        assert_eq!(rate.to, "EUR");
+        assert_eq!(rate.rate, 0.9123);
+    }
+
+    #[test]
+    fn parses_fx_time_series() {
+        let body = r#"{
+            "Meta Data": {
+                "1. Information": "FX Daily",
+                "5. Time Zone": "UTC"
+            },
+            "Time Series FX (Daily)": {
+                "2024-01-02": {
+                    "1. open": "1.10",
+                    "2. high": "1.12",
+                    "3. low": "1.09",
+                    "4. close": "1.11"
+                }
+            }
+        }"#;
+        let series = parse_currency_time_series(body).unwrap();
+        assert_eq!(series.time_zone, "UTC");
+        assert_eq!(series.prices.len(), 1);
+        assert_eq!(series.prices[0].close, 1.11);
+    }
+
+    #[test]
+    fn parses_digital_currency_time_series() {
+        let body = r#"{
+            "Meta Data": {
+                "1. Information": "Daily Prices and Volumes for Digital Currency",
+                "2. Digital Currency Code": "BTC",
+                "4. Market Code": "USD",
+                "7. Time Zone": "UTC"
+            },
+            "Time Series (Digital Currency Daily)": {
+                "2024-01-02": {
+                    "1a. open (USD)": "42000.12",
+                    "1b. open (USD)": "42000.12",
+                    "2a. high (USD)": "42500.00",
+                    "2b. high (USD)": "42500.00",
+                    "3a. low (USD)": "41000.00",
+                    "3b. low (USD)": "41000.00",
+                    "4a. close (USD)": "42250.00",
+                    "4b. close (USD)": "42250.00",
+                    "5. volume": "1234.5",
+                    "6. market cap (USD)": "800000000000.0"
+                }
+            }
+        }"#;
+        let series = parse_digital_currency_time_series(body).unwrap();
+        assert_eq!(series.time_zone, "UTC");
+        assert_eq!(series.prices.len(), 1);
+        assert_eq!(series.prices[0].open, 42000.12);
+        assert_eq!(series.prices[0].close, 42250.00);
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::QaError;
+use crate::serde_util::str_as_f64;
+use crate::third_party_api::alpha_vantage::api_error_in;
+
+/// A single result from the `SYMBOL_SEARCH` Alpha Vantage function: a tradable symbol
+/// matching a free-text search, along with Alpha Vantage's confidence that it is a match.
+#[derive(Debug, Clone)]
+pub struct SymbolMatch {
+    pub symbol: String,
+    pub name: String,
+    pub stock_type: String,
+    pub region: String,
+    pub market_open: String,
+    pub market_close: String,
+    pub time_zone: String,
+    pub currency: String,
+    pub match_score: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSymbolMatch {
+    #[serde(rename = "1. symbol")]
+    symbol: String,
+    #[serde(rename = "2. name")]
+    name: String,
+    #[serde(rename = "3. type")]
+    stock_type: String,
+    #[serde(rename = "4. region")]
+    region: String,
+    #[serde(rename = "5. marketOpen")]
+    market_open: String,
+    #[serde(rename = "6. marketClose")]
+    market_close: String,
+    #[serde(rename = "7. timezone")]
+    time_zone: String,
+    #[serde(rename = "8. currency")]
+    currency: String,
+    #[serde(rename = "9. matchScore", deserialize_with = "str_as_f64")]
+    match_score: f64,
+}
+
+impl From<RawSymbolMatch> for SymbolMatch {
+    fn from(raw: RawSymbolMatch) -> Self {
+        SymbolMatch {
+            symbol: raw.symbol,
+            name: raw.name,
+            stock_type: raw.stock_type,
+            region: raw.region,
+            market_open: raw.market_open,
+            market_close: raw.market_close,
+            time_zone: raw.time_zone,
+            currency: raw.currency,
+            match_score: raw.match_score,
+        }
+    }
+}
+
+/// Parses a `SYMBOL_SEARCH` response body into its list of matches.
+///
+/// # Errors
+///
+/// Returns a [`QaError`] if Alpha Vantage reported an in-body error, if `body` is not
+/// valid JSON, or if it does not contain a `"bestMatches"` array.
+pub fn parse_symbol_matches(body: &str) -> Result<Vec<SymbolMatch>> {
+    if let Some(err) = api_error_in(body) {
+        return Err(err.into());
+    }
+
+    let value: Value = serde_json::from_str(body)
+        .map_err(|e| QaError::Parse(format!("response body was not valid JSON: {e}")))?;
+
+    let raw_matches = value
+        .get("bestMatches")
+        .ok_or_else(|| QaError::Parse("response is missing a \"bestMatches\" array".to_string()))?;
+
+    let raw_matches: Vec<RawSymbolMatch> = serde_json::from_value(raw_matches.clone())
+        .map_err(|e| QaError::Parse(format!("failed to parse \"bestMatches\" array: {e}")))?;
+
+    Ok(raw_matches.into_iter().map(SymbolMatch::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_best_matches() {
+        let body = r#"{
+            "bestMatches": [
+                {
+                    "1. symbol": "BA",
+                    "2. name": "Boeing Company",
+                    "3. type": "Equity",
+                    "4. region": "United States",
+                    "5. marketOpen": "09:30",
+                    "6. marketClose": "16:00",
+                    "7. timezone": "UTC-04",
+                    "8. currency": "USD",
+                    "9. matchScore": "1.0000"
+                }
+            ]
+        }"#;
+
+        let matches = parse_symbol_matches(body).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].symbol, "BA");
+        assert_eq!(matches[0].match_score, 1.0);
+    }
+
+    #[test]
+    fn returns_api_error_instead_of_parsing() {
+        let body = r#"{"Error Message": "Invalid API call."}"#;
+        assert!(parse_symbol_matches(body).is_err());
+    }
+
+    #[test]
+    fn errors_when_best_matches_is_missing() {
+        let body = r#"{}"#;
+        assert!(parse_symbol_matches(body).is_err());
+    }
+}
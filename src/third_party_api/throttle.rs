@@ -0,0 +1,130 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Tracks recent request timestamps to enforce an optional requests-per-minute ceiling,
+/// and caps how many times a rate-limited request is retried with exponential backoff.
+///
+/// Given Alpha Vantage's strict free-tier limits (5 requests/minute, 25/day), this lets a
+/// [`Client`](super::alpha_vantage::Client) smooth out batch workloads that iterate over
+/// many symbols instead of immediately failing on the first `"Note"` response.
+#[derive(Debug)]
+pub(crate) struct Throttle {
+    requests_per_minute: Option<u32>,
+    max_retries: u32,
+    recent_requests: Mutex<VecDeque<Instant>>,
+}
+
+impl Throttle {
+    pub(crate) fn new(requests_per_minute: Option<u32>, max_retries: u32) -> Self {
+        Throttle {
+            requests_per_minute,
+            max_retries,
+            recent_requests: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// If issuing another request right now would exceed the configured
+    /// requests-per-minute ceiling, returns how long to wait before it wouldn't; otherwise
+    /// records the request and returns `None`. Shared by the async and blocking waiters so
+    /// the window-tracking logic isn't duplicated between them.
+    fn wait_duration(&self) -> Option<Duration> {
+        let limit = self.requests_per_minute?;
+        if limit == 0 {
+            return None;
+        }
+        let limit = limit as usize;
+        let window = Duration::from_secs(60);
+
+        let mut recent = self.recent_requests.lock().unwrap();
+        let now = Instant::now();
+        while matches!(recent.front(), Some(t) if now.duration_since(*t) >= window) {
+            recent.pop_front();
+        }
+
+        if recent.len() < limit {
+            recent.push_back(now);
+            None
+        } else {
+            Some(window - now.duration_since(*recent.front().expect("recent is non-empty: len() >= limit > 0 was just checked")))
+        }
+    }
+
+    /// Waits until issuing another request would not exceed the configured
+    /// requests-per-minute ceiling, then records the request. A no-op if no ceiling was
+    /// configured.
+    pub(crate) async fn wait_for_slot(&self) {
+        while let Some(duration) = self.wait_duration() {
+            tokio::time::sleep(duration).await;
+        }
+    }
+
+    /// The blocking counterpart to [`Throttle::wait_for_slot`], for use outside an async
+    /// runtime (e.g. by the `blocking` feature's client).
+    pub(crate) fn wait_for_slot_blocking(&self) {
+        while let Some(duration) = self.wait_duration() {
+            std::thread::sleep(duration);
+        }
+    }
+
+    /// The exponential backoff delay before retry attempt `attempt` (1-indexed).
+    pub(crate) fn backoff(attempt: u32) -> Duration {
+        Duration::from_secs(2u64.saturating_pow(attempt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_per_attempt() {
+        assert_eq!(Throttle::backoff(1), Duration::from_secs(2));
+        assert_eq!(Throttle::backoff(2), Duration::from_secs(4));
+        assert_eq!(Throttle::backoff(3), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn backoff_saturates_instead_of_overflowing() {
+        assert_eq!(Throttle::backoff(u32::MAX), Duration::from_secs(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn wait_for_slot_is_a_no_op_without_a_configured_limit() {
+        let throttle = Throttle::new(None, 0);
+        throttle.wait_for_slot().await;
+        throttle.wait_for_slot().await;
+    }
+
+    #[tokio::test]
+    async fn wait_for_slot_does_not_block_under_the_limit() {
+        let throttle = Throttle::new(Some(5), 0);
+        for _ in 0..5 {
+            throttle.wait_for_slot().await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_for_slot_waits_out_the_window_once_the_limit_is_reached() {
+        let throttle = std::sync::Arc::new(Throttle::new(Some(1), 0));
+
+        throttle.wait_for_slot().await;
+
+        let second_call = tokio::spawn({
+            let throttle = throttle.clone();
+            async move { throttle.wait_for_slot().await }
+        });
+        tokio::task::yield_now().await;
+
+        tokio::time::advance(Duration::from_secs(59)).await;
+        assert!(!second_call.is_finished(), "should still be waiting for the 60s window to clear");
+
+        tokio::time::advance(Duration::from_secs(2)).await;
+        second_call.await.expect("second call should complete once the window clears");
+    }
+}